@@ -0,0 +1,239 @@
+//! Evolutionary search for "interesting" starting seeds: a small population
+//! of candidate grids is repeatedly evaluated, selected, and bred, in the
+//! spirit of the Population/genetic-algorithm loop from the asteroids-genetic
+//! app. The current best candidate is stamped live into the main grid.
+
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+
+use crate::rule::Rule;
+use crate::state::AppState;
+use crate::CellGrid;
+
+/// Candidates are small fixed-size bitmask regions so evaluation stays
+/// cheap even with a sizeable population.
+pub const CANDIDATE_SIZE: usize = 20;
+/// Extra dead space around the candidate so spaceships have room to move
+/// during evaluation instead of hitting the sandbox edge immediately.
+const SANDBOX_MARGIN: usize = 15;
+const SANDBOX_SIZE: usize = CANDIDATE_SIZE + 2 * SANDBOX_MARGIN;
+
+const POPULATION_SIZE: usize = 30;
+const EVAL_GENERATIONS: usize = 60;
+const SELECTION_FRACTION: f32 = 0.2;
+const MUTATION_RATE: f64 = 0.02;
+const GENERATION_INTERVAL: f32 = 1.0;
+
+/// Where the best candidate is stamped for preview, well outside the
+/// `GridConfig` default seeded area (`[0, width) x [0, height)`) so showing
+/// it doesn't clobber the user's own soup.
+const DISPLAY_ORIGIN: (i64, i64) = (-(CANDIDATE_SIZE as i64) - 10, -(CANDIDATE_SIZE as i64) - 10);
+
+#[derive(Clone)]
+pub struct Candidate {
+    pub cells: Vec<Vec<bool>>,
+    pub fitness: f32,
+}
+
+impl Candidate {
+    fn random() -> Self {
+        let cells = (0..CANDIDATE_SIZE)
+            .map(|_| {
+                (0..CANDIDATE_SIZE)
+                    .map(|_| rand::random_bool(0.3))
+                    .collect()
+            })
+            .collect();
+        Self { cells, fitness: 0.0 }
+    }
+}
+
+#[derive(Resource)]
+pub struct Population {
+    pub candidates: Vec<Candidate>,
+    pub generation: usize,
+    pub running: bool,
+    timer: Timer,
+}
+
+impl Default for Population {
+    fn default() -> Self {
+        Self {
+            candidates: (0..POPULATION_SIZE).map(|_| Candidate::random()).collect(),
+            generation: 0,
+            running: false,
+            timer: Timer::from_seconds(GENERATION_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+pub struct GeneticPlugin;
+
+impl Plugin for GeneticPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Population>().add_systems(
+            Update,
+            (toggle_genetic_search, run_generation, display_best_candidate)
+                .run_if(in_state(AppState::Running)),
+        );
+    }
+}
+
+fn toggle_genetic_search(kb_input: Res<ButtonInput<KeyCode>>, mut population: ResMut<Population>) {
+    if kb_input.just_pressed(KeyCode::KeyG) {
+        population.running = !population.running;
+        population.timer.reset();
+    }
+}
+
+fn run_generation(time: Res<Time>, rule: Res<Rule>, mut population: ResMut<Population>) {
+    if !population.running {
+        return;
+    }
+
+    population.timer.tick(time.delta());
+    if !population.timer.just_finished() {
+        return;
+    }
+
+    for candidate in &mut population.candidates {
+        candidate.fitness = evaluate(&candidate.cells, &rule, EVAL_GENERATIONS);
+    }
+    population
+        .candidates
+        .sort_by(|a, b| b.fitness.partial_cmp(&a.fitness).unwrap());
+
+    let survivor_count = ((POPULATION_SIZE as f32 * SELECTION_FRACTION).ceil() as usize).max(2);
+    let survivors = population.candidates[..survivor_count].to_vec();
+
+    let mut next_generation = survivors.clone();
+    while next_generation.len() < POPULATION_SIZE {
+        let parent_a = &survivors[rand::random_range(0..survivors.len())];
+        let parent_b = &survivors[rand::random_range(0..survivors.len())];
+        next_generation.push(crossover(parent_a, parent_b));
+    }
+
+    population.candidates = next_generation;
+    population.generation += 1;
+}
+
+/// Runs `generations` ticks of `rule` over a freshly cleared sandbox seeded
+/// with `seed`, centered so the pattern has room to grow or move. Scores
+/// the peak live-cell count seen, which rewards both still-growing patterns
+/// and oscillators/spaceships without requiring a dedicated displacement
+/// metric.
+fn evaluate(seed: &[Vec<bool>], rule: &Rule, generations: usize) -> f32 {
+    let mut current = vec![vec![false; SANDBOX_SIZE]; SANDBOX_SIZE];
+    for (y, row) in seed.iter().enumerate() {
+        for (x, &alive) in row.iter().enumerate() {
+            current[x + SANDBOX_MARGIN][y + SANDBOX_MARGIN] = alive;
+        }
+    }
+    let mut next = vec![vec![false; SANDBOX_SIZE]; SANDBOX_SIZE];
+
+    let mut peak_alive = count_alive(&current);
+
+    for _ in 0..generations {
+        for x in 0..SANDBOX_SIZE {
+            for y in 0..SANDBOX_SIZE {
+                let alive_neighbors = count_alive_neighbors_bounded(&current, x, y);
+                next[x][y] = rule.next_state(current[x][y], alive_neighbors);
+            }
+        }
+        std::mem::swap(&mut current, &mut next);
+        peak_alive = peak_alive.max(count_alive(&current));
+    }
+
+    peak_alive as f32
+}
+
+fn count_alive_neighbors_bounded(grid: &[Vec<bool>], x: usize, y: usize) -> usize {
+    let mut count = 0;
+    for dx in [-1i32, 0, 1] {
+        for dy in [-1i32, 0, 1] {
+            if dx == 0 && dy == 0 {
+                continue;
+            }
+            let nx = x as i32 + dx;
+            let ny = y as i32 + dy;
+            if nx >= 0
+                && ny >= 0
+                && (nx as usize) < SANDBOX_SIZE
+                && (ny as usize) < SANDBOX_SIZE
+                && grid[nx as usize][ny as usize]
+            {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+fn count_alive(grid: &[Vec<bool>]) -> usize {
+    grid.iter().flatten().filter(|&&alive| alive).count()
+}
+
+fn crossover(parent_a: &Candidate, parent_b: &Candidate) -> Candidate {
+    let cells = parent_a
+        .cells
+        .iter()
+        .zip(&parent_b.cells)
+        .map(|(row_a, row_b)| {
+            row_a
+                .iter()
+                .zip(row_b)
+                .map(|(&a, &b)| {
+                    let gene = if rand::random_bool(0.5) { a } else { b };
+                    if rand::random_bool(MUTATION_RATE) {
+                        !gene
+                    } else {
+                        gene
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    Candidate { cells, fitness: 0.0 }
+}
+
+/// Stamps the current best candidate's seed into the main grid at
+/// `DISPLAY_ORIGIN` so it's visible while the search runs, redrawn every
+/// time a new generation's best is found. Only touches the cells it last
+/// stamped there (tracked in `displayed`), rather than clearing the whole
+/// grid, so it doesn't wipe out the soup `setup_cells` seeded or whatever
+/// the user has stamped/edited elsewhere.
+fn display_best_candidate(
+    population: Res<Population>,
+    mut grid: ResMut<CellGrid>,
+    mut displayed: Local<HashSet<(i64, i64)>>,
+) {
+    if !population.running {
+        for cell in displayed.drain() {
+            grid.live.remove(&cell);
+        }
+        return;
+    }
+
+    if !population.is_changed() || population.candidates.is_empty() {
+        return;
+    }
+
+    let best = &population.candidates[0];
+
+    for cell in displayed.drain() {
+        grid.live.remove(&cell);
+    }
+
+    let (origin_x, origin_y) = DISPLAY_ORIGIN;
+    for (y, row) in best.cells.iter().enumerate() {
+        for (x, &alive) in row.iter().enumerate() {
+            if alive {
+                let cell = (origin_x + x as i64, origin_y + y as i64);
+                grid.live.insert(cell);
+                displayed.insert(cell);
+            }
+        }
+    }
+}
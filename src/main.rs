@@ -1,32 +1,64 @@
 #![allow(clippy::needless_range_loop)]
 
+mod genetic;
+mod net;
+mod pattern;
+mod rule;
+mod state;
+mod ui;
+
+use std::collections::{HashMap, HashSet};
+
 use bevy::prelude::*;
 
-const GRID_WIDTH: usize = 200;
-const GRID_HEIGHT: usize = 200;
+use rule::Rule;
+use state::{AppState, GridConfig, Topology};
+use ui::SeedDensity;
+
 const CELL_SIZE: f32 = 10.0;
 const CAMERA_SPEED: f32 = 300.;
 const AUTO_STEP_INTERVAL: f32 = 0.2;
-
+const STAMP_PATTERN_PATH: &str = "assets/patterns/glider.rle";
+const EXPORT_PATTERN_PATH: &str = "assets/patterns/export.rle";
+const EXPORT_PATTERN_JSON5_PATH: &str = "assets/patterns/export.json5";
+const RULE_CONFIG_PATH: &str = "assets/config/rules.json5";
+const SYNC_TEST_CHECK_DISTANCE: usize = 7;
+const FAST_FORWARD_MULTIPLIER: u32 = 5;
+
+/// Marks the sprite entity spawned for a live cell currently on screen.
+/// Sprites are spawned and despawned by `sync_visible_cells` as the camera
+/// moves, so at any time there's one `Cell` per on-screen live cell rather
+/// than one per grid slot.
 #[derive(Component)]
 struct Cell {
-    x: usize,
-    y: usize,
-    alive: bool,
+    x: i64,
+    y: i64,
 }
 
-#[derive(Resource)]
+/// The simulation's live cells, stored sparsely so the world can grow far
+/// past what would fit in a dense `Vec<Vec<bool>>` and so memory cost tracks
+/// population instead of world size.
+#[derive(Resource, Clone, Default)]
 struct CellGrid {
-    current: Vec<Vec<bool>>,
-    next: Vec<Vec<bool>>,
+    live: HashSet<(i64, i64)>,
+}
+
+/// Rendering assets shared by every cell sprite, kept separate from
+/// `CellGrid` so the simulation state stays plain data (and cheap to clone
+/// for GGRS rollback).
+#[derive(Resource)]
+struct CellAssets {
+    mesh: Handle<Mesh>,
     alive_material: Handle<ColorMaterial>,
-    dead_material: Handle<ColorMaterial>,
 }
 
 #[derive(Resource)]
 struct GameState {
     auto_play: bool,
     timer: Timer,
+    step_interval: f32,
+    step_requested: bool,
+    fast_forward: bool,
 }
 
 impl Default for GameState {
@@ -34,6 +66,9 @@ impl Default for GameState {
         Self {
             auto_play: false,
             timer: Timer::from_seconds(AUTO_STEP_INTERVAL, TimerMode::Repeating),
+            step_interval: AUTO_STEP_INTERVAL,
+            step_requested: false,
+            fast_forward: false,
         }
     }
 }
@@ -41,77 +76,214 @@ impl Default for GameState {
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
+        .add_plugins(state::StatePlugin)
+        .add_plugins(net::NetPlugin)
+        .add_plugins(ui::ControlPanelPlugin)
+        .add_plugins(genetic::GeneticPlugin)
         .init_resource::<GameState>()
-        .add_systems(Startup, (setup_cells, setup_camera))
+        .add_systems(Startup, (setup_rule_library, spawn_camera))
+        .add_systems(
+            OnEnter(AppState::Running),
+            (reset_game_state, setup_cells, center_camera_on_grid)
+                .run_if(not(resource_exists::<CellGrid>())),
+        )
         .add_systems(
             Update,
             (
-                move_camera,
                 toggle_auto_play,
-                reset_grid,
-                auto_step_game_of_life,
-                update_cell_materials,
-            ),
+                // While a GGRS session is live, `GgrsSchedule` (running
+                // `network_step_game_of_life`) is the only thing allowed to
+                // advance the grid, so the two steppers don't double-step it.
+                auto_step_game_of_life
+                    .run_if(not(resource_exists::<bevy_ggrs::Session<net::Config>>())),
+                // `Rule` is rollback-registered, so mutating it here while a
+                // session is live would happen outside rollback and desync
+                // the checksum the next time GGRS replays past this frame.
+                cycle_rule.run_if(not(resource_exists::<bevy_ggrs::Session<net::Config>>())),
+                start_sync_test_session,
+            )
+                .run_if(in_state(AppState::Running)),
+        )
+        .add_systems(
+            Update,
+            (
+                move_camera,
+                // `CellGrid` is rollback-registered, so these two would also
+                // desync a live session for the same reason as `cycle_rule`
+                // above.
+                reset_grid.run_if(not(resource_exists::<bevy_ggrs::Session<net::Config>>())),
+                stamp_pattern_at_camera
+                    .run_if(not(resource_exists::<bevy_ggrs::Session<net::Config>>())),
+                export_pattern_to_rle,
+                export_pattern_to_json5,
+                sync_visible_cells,
+            )
+                .run_if(in_state(AppState::Running).or_else(in_state(AppState::Paused))),
+        )
+        .add_systems(Update, toggle_paused.run_if(not(in_state(AppState::Menu))))
+        .add_systems(Update, return_to_menu.run_if(in_state(AppState::Paused)))
+        .add_systems(
+            OnEnter(AppState::Menu),
+            despawn_game.run_if(resource_exists::<CellGrid>()),
         )
         .run();
 }
 
+/// Returns to the menu on Backspace while paused, so a new game can be
+/// started with different settings without restarting the app.
+fn return_to_menu(kb_input: Res<ButtonInput<KeyCode>>, mut next_state: ResMut<NextState<AppState>>) {
+    if kb_input.just_pressed(KeyCode::Backspace) {
+        next_state.set(AppState::Menu);
+    }
+}
+
+/// Tears down the previous game's cell entities and grid so entering
+/// `Running` again spawns a fresh one at the newly chosen settings. The
+/// camera is spawned once at `Startup` and lives across every state (the
+/// `Menu` UI needs it to render to), so it isn't touched here.
+fn despawn_game(mut commands: Commands, cells: Query<Entity, With<Cell>>) {
+    for entity in &cells {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<CellGrid>();
+}
+
+/// Toggles between `Running` and `Paused` on Escape, freezing stepping
+/// while still allowing camera movement and grid editing.
+fn toggle_paused(
+    kb_input: Res<ButtonInput<KeyCode>>,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if !kb_input.just_pressed(KeyCode::Escape) {
+        return;
+    }
+
+    match state.get() {
+        AppState::Running => next_state.set(AppState::Paused),
+        AppState::Paused => next_state.set(AppState::Running),
+        AppState::Menu => {}
+    }
+}
+
+/// Starts a local two-player `SyncTestSession` on keypress, which replays
+/// every frame with rollback enabled to catch determinism bugs in
+/// `advance_generation` before they'd cause a desync between two real
+/// machines.
+fn start_sync_test_session(mut commands: Commands, kb_input: Res<ButtonInput<KeyCode>>) {
+    if !kb_input.just_pressed(KeyCode::KeyN) {
+        return;
+    }
+
+    match net::build_sync_test_session(2, SYNC_TEST_CHECK_DISTANCE) {
+        Ok(session) => {
+            commands.insert_resource(bevy_ggrs::Session::SyncTest(session));
+            info!("started GGRS sync test session");
+        }
+        Err(err) => warn!("failed to start sync test session: {err}"),
+    }
+}
+
+/// Resets simulation speed/play state to defaults each time a game starts.
+/// `GameState` itself is set up by `init_resource` at app build time (rather
+/// than inserted here) so it's available the instant `OnEnter(Running)`
+/// fires, before this and `spawn_control_panel`'s deferred commands would
+/// otherwise race to flush first.
+fn reset_game_state(mut game_state: ResMut<GameState>) {
+    *game_state = GameState::default();
+}
+
+/// Loads the rule library from `RULE_CONFIG_PATH` and makes its active rule
+/// available as the `Rule` resource the step function reads.
+fn setup_rule_library(mut commands: Commands) {
+    let library = match rule::load_rule_library(RULE_CONFIG_PATH) {
+        Ok(library) => library,
+        Err(err) => {
+            warn!("failed to load rule config from {RULE_CONFIG_PATH}: {err}, falling back to B3/S23");
+            rule::RuleLibrary {
+                rules: vec![Rule::default()],
+                active: 0,
+            }
+        }
+    };
+
+    commands.insert_resource(library.active_rule().clone());
+    commands.insert_resource(library);
+}
+
+/// Cycles to the next rule in the library on keypress, so users can flip
+/// between e.g. Conway's Life, HighLife, and Seeds without restarting.
+fn cycle_rule(
+    kb_input: Res<ButtonInput<KeyCode>>,
+    mut library: ResMut<rule::RuleLibrary>,
+    mut active_rule: ResMut<Rule>,
+) {
+    if !kb_input.just_pressed(KeyCode::KeyL) {
+        return;
+    }
+
+    library.active = (library.active + 1) % library.rules.len();
+    *active_rule = library.active_rule().clone();
+    info!("switched to rule {}", active_rule.name);
+}
+
 fn setup_cells(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    seed_density: Res<SeedDensity>,
+    grid_config: Res<GridConfig>,
 ) {
     let mesh = meshes.add(Rectangle::new(CELL_SIZE - 1.0, CELL_SIZE - 1.0));
     let alive_material = materials.add(ColorMaterial::from(Color::WHITE));
-    let dead_material = materials.add(ColorMaterial::from(Color::BLACK));
 
-    let mut current = vec![vec![false; GRID_HEIGHT]; GRID_WIDTH];
-    let next = vec![vec![false; GRID_HEIGHT]; GRID_WIDTH];
+    let live = seeded_live_cells(&grid_config, &seed_density);
 
-    for x in 0..GRID_WIDTH {
-        for y in 0..GRID_HEIGHT {
-            let alive = rand::random_bool(0.1);
-            current[x][y] = alive;
-
-            let material = if alive {
-                alive_material.clone()
-            } else {
-                dead_material.clone()
-            };
-
-            commands.spawn((
-                Mesh2d(mesh.clone()),
-                MeshMaterial2d(material),
-                Transform::from_xyz(x as f32 * CELL_SIZE, y as f32 * CELL_SIZE, 0.0),
-                GlobalTransform::default(),
-                Cell { x, y, alive },
-            ));
-        }
-    }
-
-    commands.insert_resource(CellGrid {
-        current,
-        next,
+    commands.insert_resource(CellGrid { live });
+    commands.insert_resource(CellAssets {
+        mesh,
         alive_material,
-        dead_material,
     });
 }
 
-fn setup_camera(mut commands: Commands) {
-    let grid_width_pixels = GRID_WIDTH as f32 * CELL_SIZE;
-    let grid_height_pixels = GRID_HEIGHT as f32 * CELL_SIZE;
+/// Randomly seeds `GridConfig`'s area at `seed_density`, used both for the
+/// initial grid and for `reset_grid`.
+fn seeded_live_cells(grid_config: &GridConfig, seed_density: &SeedDensity) -> HashSet<(i64, i64)> {
+    (0..grid_config.width as i64)
+        .flat_map(|x| (0..grid_config.height as i64).map(move |y| (x, y)))
+        .filter(|_| rand::random_bool(seed_density.0 as f64))
+        .collect()
+}
 
+/// Spawns the single `Camera2d` at `Startup`, so it exists before the app
+/// ever reaches `AppState::Menu` (`bevy_ui` needs a camera to render the
+/// start menu to) and keeps living across every later state transition.
+fn spawn_camera(mut commands: Commands) {
     commands.spawn((
         Camera2d,
         Camera {
             hdr: true,
             ..default()
         },
-        Transform::from_xyz(grid_width_pixels / 2.0, grid_height_pixels / 2.0, 0.0)
-            .with_scale(Vec3::new(0.1, 0.1, 1.0)),
+        Transform::from_scale(Vec3::new(0.1, 0.1, 1.0)),
     ));
 }
 
+/// Recenters the persistent camera on the newly chosen grid when a fresh
+/// game starts.
+fn center_camera_on_grid(
+    mut camera_query: Query<&mut Transform, With<Camera2d>>,
+    grid_config: Res<GridConfig>,
+) {
+    let grid_width_pixels = grid_config.width as f32 * CELL_SIZE;
+    let grid_height_pixels = grid_config.height as f32 * CELL_SIZE;
+
+    if let Ok(mut transform) = camera_query.get_single_mut() {
+        transform.translation.x = grid_width_pixels / 2.0;
+        transform.translation.y = grid_height_pixels / 2.0;
+    }
+}
+
 fn move_camera(
     mut camera_query: Query<&mut Transform, With<Camera2d>>,
     time: Res<Time>,
@@ -172,94 +344,236 @@ fn toggle_auto_play(kb_input: Res<ButtonInput<KeyCode>>, mut game_state: ResMut<
 fn reset_grid(
     kb_input: Res<ButtonInput<KeyCode>>,
     mut grid: ResMut<CellGrid>,
-    mut query: Query<&mut Cell>,
+    seed_density: Res<SeedDensity>,
+    grid_config: Res<GridConfig>,
 ) {
     if kb_input.just_pressed(KeyCode::KeyR) {
-        for x in 0..GRID_WIDTH {
-            for y in 0..GRID_HEIGHT {
-                let alive = rand::random_bool(0.1);
-                grid.current[x][y] = alive;
-                grid.next[x][y] = alive;
+        grid.live = seeded_live_cells(&grid_config, &seed_density);
+    }
+}
+
+/// Resolves a raw coordinate to the canonical cell it maps to under
+/// `topology`, or `None` if `topology` excludes it (outside `grid_config`'s
+/// bounds in `Bounded` mode). Shared by `advance_generation` (to find a
+/// live cell's neighbors) and the pattern-stamping system (to place cells).
+fn place_cell(x: i64, y: i64, topology: Topology, grid_config: &GridConfig) -> Option<(i64, i64)> {
+    let width = grid_config.width as i64;
+    let height = grid_config.height as i64;
+
+    match topology {
+        Topology::Infinite => Some((x, y)),
+        Topology::Toroidal => Some((x.rem_euclid(width), y.rem_euclid(height))),
+        Topology::Bounded => {
+            if x >= 0 && y >= 0 && x < width && y < height {
+                Some((x, y))
+            } else {
+                None
             }
         }
+    }
+}
 
-        for mut cell in &mut query {
-            cell.alive = grid.current[cell.x][cell.y];
+/// Advances `grid` by exactly one generation under `rule` and `topology`,
+/// tallying neighbor counts only around currently-live cells (and their
+/// neighbors) rather than scanning every cell in the world.
+fn advance_generation(grid: &mut CellGrid, rule: &Rule, topology: Topology, grid_config: &GridConfig) {
+    let mut neighbor_counts: HashMap<(i64, i64), u8> = HashMap::new();
+
+    for &(x, y) in &grid.live {
+        neighbor_counts.entry((x, y)).or_insert(0);
+
+        for dx in [-1i64, 0, 1] {
+            for dy in [-1i64, 0, 1] {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+
+                if let Some(neighbor) = place_cell(x + dx, y + dy, topology, grid_config) {
+                    *neighbor_counts.entry(neighbor).or_insert(0) += 1;
+                }
+            }
         }
     }
+
+    grid.live = neighbor_counts
+        .into_iter()
+        .filter(|&(cell, count)| rule.next_state(grid.live.contains(&cell), count as usize))
+        .map(|(cell, _)| cell)
+        .collect();
 }
 
 fn auto_step_game_of_life(
     time: Res<Time>,
     mut game_state: ResMut<GameState>,
     mut grid: ResMut<CellGrid>,
-    mut query: Query<&mut Cell>,
+    rule: Res<Rule>,
+    topology: Res<Topology>,
+    grid_config: Res<GridConfig>,
 ) {
-    if !game_state.auto_play {
-        return;
+    if game_state.step_requested {
+        advance_generation(&mut grid, &rule, *topology, &grid_config);
+        game_state.step_requested = false;
     }
 
-    game_state.timer.tick(time.delta());
+    if game_state.auto_play {
+        game_state.timer.tick(time.delta());
 
-    if game_state.timer.just_finished() {
-        for x in 0..GRID_WIDTH {
-            for y in 0..GRID_HEIGHT {
-                let alive_neighbors = count_alive_neighbors(&grid.current, x, y);
-                let alive = grid.current[x][y];
-
-                grid.next[x][y] = matches!((alive, alive_neighbors), (true, 2..=3) | (false, 3));
+        if game_state.timer.just_finished() {
+            let steps = if game_state.fast_forward {
+                FAST_FORWARD_MULTIPLIER
+            } else {
+                1
+            };
+            for _ in 0..steps {
+                advance_generation(&mut grid, &rule, *topology, &grid_config);
             }
         }
+    }
+}
 
-        for x in 0..GRID_WIDTH {
-            for y in 0..GRID_HEIGHT {
-                grid.current[x][y] = grid.next[x][y];
-            }
+/// Loads the pattern at `STAMP_PATTERN_PATH` and stamps its live cells onto
+/// the grid, with the pattern's own origin placed on the cell under the
+/// camera's current position. Picks RLE or JSON5 based on the file extension.
+fn stamp_pattern_at_camera(
+    kb_input: Res<ButtonInput<KeyCode>>,
+    camera_query: Query<&Transform, With<Camera2d>>,
+    mut grid: ResMut<CellGrid>,
+    topology: Res<Topology>,
+    grid_config: Res<GridConfig>,
+) {
+    if !kb_input.just_pressed(KeyCode::KeyP) {
+        return;
+    }
+
+    let live_cells = match load_pattern_cells(STAMP_PATTERN_PATH) {
+        Ok(cells) => cells,
+        Err(err) => {
+            warn!("failed to load pattern from {STAMP_PATTERN_PATH}: {err}");
+            return;
         }
-        for mut cell in &mut query {
-            cell.alive = grid.current[cell.x][cell.y];
+    };
+
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+    let center_x = (camera_transform.translation.x / CELL_SIZE).round() as i64;
+    let center_y = (camera_transform.translation.y / CELL_SIZE).round() as i64;
+
+    for (dx, dy) in live_cells {
+        if let Some(cell) = place_cell(center_x + dx as i64, center_y + dy as i64, *topology, &grid_config) {
+            grid.live.insert(cell);
         }
     }
 }
 
-fn count_alive_neighbors(grid: &[Vec<bool>], x: usize, y: usize) -> usize {
-    let mut count = 0;
+fn load_pattern_cells(path: &str) -> Result<Vec<(i32, i32)>, pattern::PatternError> {
+    let data = if path.ends_with(".json5") || path.ends_with(".json") {
+        pattern::load_json5_file(path)?
+    } else {
+        pattern::load_rle_file(path)?
+    };
 
-    for dx in [-1i32, 0, 1] {
-        for dy in [-1i32, 0, 1] {
-            if dx == 0 && dy == 0 {
-                continue;
-            }
+    Ok(data.live_cells.into_iter().map(|[x, y]| (x, y)).collect())
+}
+
+/// Writes the grid's current live cells back out as an RLE file so a run
+/// can be shared or resumed later.
+fn export_pattern_to_rle(
+    kb_input: Res<ButtonInput<KeyCode>>,
+    grid: Res<CellGrid>,
+    rule: Res<Rule>,
+) {
+    if !kb_input.just_pressed(KeyCode::KeyO) {
+        return;
+    }
 
-            let nx = x as i32 + dx;
-            let ny = y as i32 + dy;
+    let live_cells: Vec<(i32, i32)> = grid.live.iter().map(|&(x, y)| (x as i32, y as i32)).collect();
 
-            if nx >= 0
-                && ny >= 0
-                && (nx as usize) < GRID_WIDTH
-                && (ny as usize) < GRID_HEIGHT
-                && grid[nx as usize][ny as usize]
-            {
-                count += 1;
-            }
-        }
+    if let Err(err) = pattern::save_rle_file(EXPORT_PATTERN_PATH, &live_cells, &rule.name) {
+        warn!("failed to export pattern to {EXPORT_PATTERN_PATH}: {err}");
+    } else {
+        info!("exported {} live cells to {EXPORT_PATTERN_PATH}", live_cells.len());
     }
+}
 
-    count
+/// Writes the grid's current live cells back out as a JSON5 pattern file,
+/// the crate's own data-file format alongside the RLE export.
+fn export_pattern_to_json5(
+    kb_input: Res<ButtonInput<KeyCode>>,
+    grid: Res<CellGrid>,
+    rule: Res<Rule>,
+) {
+    if !kb_input.just_pressed(KeyCode::KeyI) {
+        return;
+    }
+
+    let live_cells: Vec<[i32; 2]> = grid.live.iter().map(|&(x, y)| [x as i32, y as i32]).collect();
+    let pattern = pattern::PatternData {
+        origin: [0, 0],
+        rule: rule.name.clone(),
+        live_cells,
+    };
+
+    if let Err(err) = pattern::save_json5_file(EXPORT_PATTERN_JSON5_PATH, &pattern) {
+        warn!("failed to export pattern to {EXPORT_PATTERN_JSON5_PATH}: {err}");
+    } else {
+        info!(
+            "exported {} live cells to {EXPORT_PATTERN_JSON5_PATH}",
+            pattern.live_cells.len()
+        );
+    }
 }
 
-fn update_cell_materials(
-    mut query: Query<(&Cell, &mut MeshMaterial2d<ColorMaterial>)>,
+/// Spawns a sprite for every live cell within the camera's viewport and
+/// despawns sprites that are no longer both live and visible, so the sprite
+/// count tracks what's on screen rather than the (possibly unbounded) world.
+fn sync_visible_cells(
+    mut commands: Commands,
     grid: Res<CellGrid>,
+    assets: Res<CellAssets>,
+    camera_query: Query<&Transform, With<Camera2d>>,
+    windows: Query<&Window>,
+    spawned: Query<(Entity, &Cell)>,
 ) {
-    for (cell, mut material) in &mut query {
-        let expected = if cell.alive {
-            &grid.alive_material
-        } else {
-            &grid.dead_material
-        };
-        if &material.0 != expected {
-            material.0 = expected.clone();
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+
+    let visible_half_width = window.width() / 2.0 * camera_transform.scale.x;
+    let visible_half_height = window.height() / 2.0 * camera_transform.scale.y;
+    let center = camera_transform.translation;
+
+    let min_x = ((center.x - visible_half_width) / CELL_SIZE).floor() as i64 - 1;
+    let max_x = ((center.x + visible_half_width) / CELL_SIZE).ceil() as i64 + 1;
+    let min_y = ((center.y - visible_half_height) / CELL_SIZE).floor() as i64 - 1;
+    let max_y = ((center.y + visible_half_height) / CELL_SIZE).ceil() as i64 + 1;
+
+    let mut stale: HashMap<(i64, i64), Entity> = spawned
+        .iter()
+        .map(|(entity, cell)| ((cell.x, cell.y), entity))
+        .collect();
+
+    for &(x, y) in &grid.live {
+        if x < min_x || x > max_x || y < min_y || y > max_y {
+            continue;
+        }
+        if stale.remove(&(x, y)).is_some() {
+            continue;
         }
+
+        commands.spawn((
+            Mesh2d(assets.mesh.clone()),
+            MeshMaterial2d(assets.alive_material.clone()),
+            Transform::from_xyz(x as f32 * CELL_SIZE, y as f32 * CELL_SIZE, 0.0),
+            GlobalTransform::default(),
+            Cell { x, y },
+        ));
+    }
+
+    for entity in stale.into_values() {
+        commands.entity(entity).despawn();
     }
 }
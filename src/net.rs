@@ -0,0 +1,166 @@
+//! Rollback-netcode foundation for a networked mode, built on GGRS: since
+//! Conway's Game of Life is fully deterministic, two peers could in
+//! principle stay bit-identical by exchanging only player inputs and
+//! letting GGRS predict, roll back, and resync.
+//!
+//! Scope note: only the local-machine half of that is wired up so far.
+//! `Config`, `BoxInput`, and `network_step_game_of_life` are real and are
+//! exercised via `build_sync_test_session`'s `SyncTestSession` (started with
+//! KeyN), which is enough to fuzz `advance_generation` for desyncs. There is
+//! no peer transport here (no socket/matchbox session bootstrap), so this
+//! module alone can't yet connect two separate machines — that's follow-up
+//! work, to be layered on top of `Config` and `network_step_game_of_life`
+//! once a transport is chosen.
+
+use bevy::prelude::*;
+use bevy_ggrs::{ggrs, GgrsApp, GgrsPlugin, GgrsSchedule, ReadInputs};
+use bytemuck::{Pod, Zeroable};
+use ggrs::{PlayerHandle, SessionBuilder, SyncTestSession};
+
+use crate::rule::Rule;
+use crate::state::{GridConfig, Topology};
+use crate::{advance_generation, place_cell, CellGrid};
+
+/// Per-player input: the cell the player's cursor is over, plus a toggle
+/// bit for whether they're flipping it this frame. `Pod`/`Zeroable` so GGRS
+/// can serialize it directly.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct BoxInput {
+    pub cell: [i32; 2],
+    pub toggle: u8,
+    _pad: [u8; 3],
+}
+
+impl BoxInput {
+    pub fn new(cell: [i32; 2], toggle: bool) -> Self {
+        Self {
+            cell,
+            toggle: toggle as u8,
+            _pad: [0; 3],
+        }
+    }
+}
+
+/// The GGRS config for this game: inputs are `BoxInput`, addresses are
+/// plain socket addresses, and `State` is left as an unused placeholder —
+/// `CellGrid` is rolled back as a Bevy resource and checksummed via
+/// `checksum_resource_with_hash` (see `checksum_cell_grid`) rather than
+/// through GGRS's own per-`State` checksum mechanism.
+pub struct Config;
+
+impl ggrs::Config for Config {
+    type Input = BoxInput;
+    type State = u8;
+    type Address = std::net::SocketAddr;
+}
+
+pub const FPS: usize = 60;
+pub const INPUT_DELAY: usize = 2;
+
+pub struct NetPlugin;
+
+impl Plugin for NetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(GgrsPlugin::<Config>::default())
+            .set_rollback_schedule_fps(FPS as u32)
+            .rollback_resource_with_clone::<CellGrid>()
+            .checksum_resource_with_hash::<CellGrid>(checksum_cell_grid)
+            .rollback_resource_with_clone::<Rule>()
+            .add_systems(ReadInputs, read_local_inputs)
+            .add_systems(GgrsSchedule, network_step_game_of_life);
+    }
+}
+
+/// Hashes `grid.live` order-independently (the `HashSet`'s own iteration
+/// order isn't stable across peers) so `SyncTestSession`'s rollback replay
+/// can actually catch a desync in `advance_generation`, instead of the grid
+/// being rolled back and restored without ever being compared.
+fn checksum_cell_grid(grid: &CellGrid) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    grid.live.iter().fold(0u64, |checksum, cell| {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        cell.hash(&mut hasher);
+        checksum ^ hasher.finish()
+    })
+}
+
+/// Reads this machine's cursor cell and spacebar toggle and hands it to
+/// GGRS as this player's input for the frame.
+fn read_local_inputs(
+    mut commands: Commands,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    kb_input: Res<ButtonInput<KeyCode>>,
+    local_players: Res<bevy_ggrs::LocalPlayers>,
+) {
+    let cell = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .and_then(|cursor| {
+            let (camera, camera_transform) = camera_query.get_single().ok()?;
+            camera.viewport_to_world_2d(camera_transform, cursor).ok()
+        })
+        .map(|world_pos| {
+            [
+                (world_pos.x / crate::CELL_SIZE).round() as i32,
+                (world_pos.y / crate::CELL_SIZE).round() as i32,
+            ]
+        })
+        .unwrap_or([0, 0]);
+
+    let toggle = kb_input.just_pressed(KeyCode::Space);
+
+    let mut local_inputs = std::collections::HashMap::new();
+    for handle in &local_players.0 {
+        local_inputs.insert(*handle, BoxInput::new(cell, toggle));
+    }
+    commands.insert_resource(bevy_ggrs::LocalInputs::<Config>(local_inputs));
+}
+
+/// The deterministic step run inside `GgrsSchedule`: apply both players'
+/// cell toggles, then advance the simulation exactly like the offline step
+/// function so replays across peers stay bit-identical.
+fn network_step_game_of_life(
+    inputs: Res<bevy_ggrs::PlayerInputs<Config>>,
+    mut grid: ResMut<CellGrid>,
+    rule: Res<Rule>,
+    topology: Res<Topology>,
+    grid_config: Res<GridConfig>,
+) {
+    for (input, _) in inputs.0.iter() {
+        if input.toggle != 0 {
+            let [x, y] = input.cell;
+            let Some(cell) = place_cell(x as i64, y as i64, *topology, &grid_config) else {
+                continue;
+            };
+            if grid.live.contains(&cell) {
+                grid.live.remove(&cell);
+            } else {
+                grid.live.insert(cell);
+            }
+        }
+    }
+
+    advance_generation(&mut grid, &rule, *topology, &grid_config);
+}
+
+/// Builds a local `SyncTestSession` with `check_distance` rollback frames,
+/// so `advance_generation` can be fuzzed for desync bugs without needing
+/// two machines.
+pub fn build_sync_test_session(
+    num_players: usize,
+    check_distance: usize,
+) -> Result<SyncTestSession<Config>, ggrs::GgrsError> {
+    let mut builder = SessionBuilder::<Config>::new()
+        .with_num_players(num_players)
+        .with_check_distance(check_distance);
+
+    for handle in 0..num_players {
+        builder = builder.add_player(ggrs::PlayerType::Local, handle as PlayerHandle)?;
+    }
+
+    builder.start_synctest_session()
+}
@@ -0,0 +1,202 @@
+//! Loading and saving Game of Life patterns from disk.
+//!
+//! Two formats are supported:
+//! - The standard Game of Life RLE format (`b`/`o`/`$`/`!` run-length tokens
+//!   with an `x = .., y = .., rule = B3/S23` header), used by most pattern
+//!   collections in the wild.
+//! - A serde/JSON5 format for patterns authored or exported by this game,
+//!   following the same data-file convention as `PlayerData`/`BlockData` in
+//!   the wedge crate.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// A pattern loaded from disk, in coordinates relative to its own origin.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PatternData {
+    pub origin: [i32; 2],
+    pub rule: String,
+    pub live_cells: Vec<[i32; 2]>,
+}
+
+#[derive(Debug)]
+pub enum PatternError {
+    Io(std::io::Error),
+    Parse(String),
+    Json5(json5::Error),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternError::Io(err) => write!(f, "pattern io error: {err}"),
+            PatternError::Parse(msg) => write!(f, "pattern parse error: {msg}"),
+            PatternError::Json5(err) => write!(f, "pattern json5 error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+impl From<std::io::Error> for PatternError {
+    fn from(err: std::io::Error) -> Self {
+        PatternError::Io(err)
+    }
+}
+
+impl From<json5::Error> for PatternError {
+    fn from(err: json5::Error) -> Self {
+        PatternError::Json5(err)
+    }
+}
+
+/// Parses the standard RLE pattern format into a `PatternData` with its
+/// origin at `[0, 0]`.
+pub fn parse_rle(input: &str) -> Result<PatternData, PatternError> {
+    let mut rule = "B3/S23".to_string();
+    let mut body = String::new();
+
+    for line in input.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with("x") {
+            if let Some(rule_part) = line.split("rule").nth(1) {
+                rule = rule_part.trim_start_matches([' ', '=']).trim().to_string();
+            }
+            continue;
+        }
+        body.push_str(line);
+    }
+
+    let mut live_cells = Vec::new();
+    let mut x = 0i32;
+    let mut y = 0i32;
+    let mut run_count = String::new();
+
+    for ch in body.chars() {
+        match ch {
+            '0'..='9' => run_count.push(ch),
+            'b' | 'o' | '$' => {
+                let count: i32 = if run_count.is_empty() {
+                    1
+                } else {
+                    run_count
+                        .parse()
+                        .map_err(|_| PatternError::Parse(format!("bad run count {run_count}")))?
+                };
+                run_count.clear();
+
+                match ch {
+                    'b' => x += count,
+                    'o' => {
+                        for _ in 0..count {
+                            live_cells.push([x, y]);
+                            x += 1;
+                        }
+                    }
+                    '$' => {
+                        y += count;
+                        x = 0;
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            '!' => break,
+            _ => return Err(PatternError::Parse(format!("unexpected token {ch:?}"))),
+        }
+    }
+
+    Ok(PatternData {
+        origin: [0, 0],
+        rule,
+        live_cells,
+    })
+}
+
+/// Serializes live cells (already relative to `origin`) into the standard
+/// RLE pattern format.
+pub fn to_rle(live_cells: &[(i32, i32)], rule: &str) -> String {
+    if live_cells.is_empty() {
+        return format!("x = 0, y = 0, rule = {rule}\n!\n");
+    }
+
+    let min_x = live_cells.iter().map(|(x, _)| *x).min().unwrap();
+    let max_x = live_cells.iter().map(|(x, _)| *x).max().unwrap();
+    let min_y = live_cells.iter().map(|(_, y)| *y).min().unwrap();
+    let max_y = live_cells.iter().map(|(_, y)| *y).max().unwrap();
+
+    let width = max_x - min_x + 1;
+    let height = max_y - min_y + 1;
+
+    let mut rows = vec![vec![false; width as usize]; height as usize];
+    for (x, y) in live_cells {
+        rows[(y - min_y) as usize][(x - min_x) as usize] = true;
+    }
+
+    let mut out = format!("x = {width}, y = {height}, rule = {rule}\n");
+    let mut line_len = 0;
+
+    for (row_idx, row) in rows.iter().enumerate() {
+        // Standard RLE omits a trailing run of dead cells at the end of a
+        // row, so only encode up to the last live cell.
+        let encoded_len = row.iter().rposition(|&alive| alive).map_or(0, |i| i + 1);
+
+        let mut col = 0;
+        while col < encoded_len {
+            let alive = row[col];
+            let start = col;
+            while col < encoded_len && row[col] == alive {
+                col += 1;
+            }
+            let run = col - start;
+            let token = if alive { 'o' } else { 'b' };
+            let chunk = if run > 1 {
+                format!("{run}{token}")
+            } else {
+                token.to_string()
+            };
+            line_len += chunk.len();
+            out.push_str(&chunk);
+            if line_len > 60 {
+                out.push('\n');
+                line_len = 0;
+            }
+        }
+        if row_idx + 1 < rows.len() {
+            out.push('$');
+        }
+    }
+    out.push_str("!\n");
+    out
+}
+
+pub fn load_rle_file(path: impl AsRef<Path>) -> Result<PatternData, PatternError> {
+    let contents = fs::read_to_string(path)?;
+    parse_rle(&contents)
+}
+
+pub fn save_rle_file(
+    path: impl AsRef<Path>,
+    live_cells: &[(i32, i32)],
+    rule: &str,
+) -> Result<(), PatternError> {
+    fs::write(path, to_rle(live_cells, rule))?;
+    Ok(())
+}
+
+pub fn load_json5_file(path: impl AsRef<Path>) -> Result<PatternData, PatternError> {
+    let contents = fs::read_to_string(path)?;
+    Ok(json5::from_str(&contents)?)
+}
+
+pub fn save_json5_file(path: impl AsRef<Path>, pattern: &PatternData) -> Result<(), PatternError> {
+    let contents =
+        json5::to_string(pattern).map_err(|err| PatternError::Parse(err.to_string()))?;
+    fs::write(path, contents)?;
+    Ok(())
+}
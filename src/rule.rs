@@ -0,0 +1,145 @@
+//! Life-like rule parsing, loaded from JSON5 config so the step function
+//! isn't hardcoded to B3/S23.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A parsed life-like rule: for each neighbor count 0..=8, whether a dead
+/// cell is born or a live cell survives.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub name: String,
+    pub birth: [bool; 9],
+    pub survival: [bool; 9],
+}
+
+impl Rule {
+    pub fn next_state(&self, alive: bool, alive_neighbors: usize) -> bool {
+        if alive {
+            self.survival[alive_neighbors]
+        } else {
+            self.birth[alive_neighbors]
+        }
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        parse_rule("B3/S23").expect("B3/S23 is a valid rule notation")
+    }
+}
+
+#[derive(Debug)]
+pub enum RuleError {
+    Io(std::io::Error),
+    Json5(json5::Error),
+    Parse(String),
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleError::Io(err) => write!(f, "rule io error: {err}"),
+            RuleError::Json5(err) => write!(f, "rule json5 error: {err}"),
+            RuleError::Parse(msg) => write!(f, "rule parse error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+impl From<std::io::Error> for RuleError {
+    fn from(err: std::io::Error) -> Self {
+        RuleError::Io(err)
+    }
+}
+
+impl From<json5::Error> for RuleError {
+    fn from(err: json5::Error) -> Self {
+        RuleError::Json5(err)
+    }
+}
+
+/// Parses standard `B{digits}/S{digits}` notation (e.g. `B3/S23`,
+/// `B36/S23`, `B2/S`) into birth/survival tables indexed by neighbor count.
+pub fn parse_rule(notation: &str) -> Result<Rule, RuleError> {
+    let (b_part, s_part) = notation
+        .split_once('/')
+        .ok_or_else(|| RuleError::Parse(format!("missing '/' in rule {notation:?}")))?;
+
+    let b_digits = b_part
+        .strip_prefix('B')
+        .or_else(|| b_part.strip_prefix('b'))
+        .ok_or_else(|| RuleError::Parse(format!("expected B prefix in {b_part:?}")))?;
+    let s_digits = s_part
+        .strip_prefix('S')
+        .or_else(|| s_part.strip_prefix('s'))
+        .ok_or_else(|| RuleError::Parse(format!("expected S prefix in {s_part:?}")))?;
+
+    let mut birth = [false; 9];
+    let mut survival = [false; 9];
+
+    for ch in b_digits.chars() {
+        let n = digit_to_index(ch)?;
+        birth[n] = true;
+    }
+    for ch in s_digits.chars() {
+        let n = digit_to_index(ch)?;
+        survival[n] = true;
+    }
+
+    Ok(Rule {
+        name: notation.to_string(),
+        birth,
+        survival,
+    })
+}
+
+fn digit_to_index(ch: char) -> Result<usize, RuleError> {
+    ch.to_digit(10)
+        .filter(|&d| d <= 8)
+        .map(|d| d as usize)
+        .ok_or_else(|| RuleError::Parse(format!("neighbor count out of range: {ch:?}")))
+}
+
+/// The set of rules that `cycle_rule` rotates through, loaded from a JSON5
+/// config at startup.
+#[derive(Resource, Debug, Clone)]
+pub struct RuleLibrary {
+    pub rules: Vec<Rule>,
+    pub active: usize,
+}
+
+impl RuleLibrary {
+    pub fn active_rule(&self) -> &Rule {
+        &self.rules[self.active]
+    }
+}
+
+/// Mirrors the on-disk JSON5 shape: a plain list of rule notations, the
+/// first of which is active on load.
+#[derive(Debug, Deserialize, Serialize)]
+struct RuleConfig {
+    rules: Vec<String>,
+}
+
+pub fn load_rule_library(path: impl AsRef<Path>) -> Result<RuleLibrary, RuleError> {
+    let contents = fs::read_to_string(path)?;
+    let config: RuleConfig = json5::from_str(&contents)?;
+
+    let rules = config
+        .rules
+        .iter()
+        .map(|notation| parse_rule(notation))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if rules.is_empty() {
+        return Err(RuleError::Parse("rule config has no rules".to_string()));
+    }
+
+    Ok(RuleLibrary { rules, active: 0 })
+}
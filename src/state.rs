@@ -0,0 +1,396 @@
+//! App lifecycle: a `Menu` where the player picks grid size, rule, topology,
+//! and seed density, a `Running` state with the simulation gated behind it,
+//! and a `Paused` overlay that freezes stepping while camera movement and
+//! grid editing stay available.
+
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+
+use crate::rule::RuleLibrary;
+use crate::ui::{SeedDensity, SliderFill, DENSITY_MAX, DENSITY_MIN};
+
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AppState {
+    #[default]
+    Menu,
+    Running,
+    Paused,
+}
+
+/// The grid dimensions chosen in the menu, read by `setup_cells`/
+/// `center_camera_on_grid` when entering `Running`.
+#[derive(Resource, Clone, Copy)]
+pub struct GridConfig {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Default for GridConfig {
+    fn default() -> Self {
+        Self {
+            width: 200,
+            height: 200,
+        }
+    }
+}
+
+/// How the world wraps at `GridConfig`'s bounds. Read by `advance_generation`
+/// and the pattern-stamping/editing systems via `place_cell`.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Topology {
+    /// Cells outside `GridConfig`'s bounds can't be born or survive.
+    Bounded,
+    /// Coordinates wrap around `GridConfig`'s bounds, so the grid is a torus.
+    #[default]
+    Toroidal,
+    /// No bounds at all; `GridConfig`'s width/height only seed the initial
+    /// starting area.
+    Infinite,
+}
+
+impl Topology {
+    pub fn label(self) -> &'static str {
+        match self {
+            Topology::Bounded => "Bounded",
+            Topology::Toroidal => "Toroidal",
+            Topology::Infinite => "Infinite",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Topology::Bounded => Topology::Toroidal,
+            Topology::Toroidal => Topology::Infinite,
+            Topology::Infinite => Topology::Bounded,
+        }
+    }
+}
+
+const GRID_SIZE_PRESETS: [(usize, usize); 3] = [(60, 60), (120, 120), (200, 200)];
+
+#[derive(Component)]
+struct MenuRoot;
+
+#[derive(Component)]
+struct GridSizeButton;
+
+#[derive(Component)]
+struct RuleButton;
+
+#[derive(Component)]
+struct TopologyButton;
+
+#[derive(Component)]
+struct StartButton;
+
+#[derive(Component)]
+struct GridSizeLabel;
+
+#[derive(Component)]
+struct RuleLabel;
+
+#[derive(Component)]
+struct TopologyLabel;
+
+#[derive(Component)]
+struct PausedOverlay;
+
+pub struct StatePlugin;
+
+impl Plugin for StatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<AppState>()
+            .init_resource::<GridConfig>()
+            .init_resource::<Topology>()
+            .add_systems(OnEnter(AppState::Menu), spawn_menu)
+            .add_systems(OnExit(AppState::Menu), despawn_menu)
+            .add_systems(
+                Update,
+                (
+                    handle_grid_size_button,
+                    handle_rule_button,
+                    handle_topology_button,
+                    handle_start_button,
+                    handle_menu_density_slider,
+                )
+                    .run_if(in_state(AppState::Menu)),
+            )
+            .add_systems(OnEnter(AppState::Paused), spawn_paused_overlay)
+            .add_systems(OnExit(AppState::Paused), despawn_paused_overlay);
+    }
+}
+
+fn spawn_menu(
+    mut commands: Commands,
+    grid_config: Res<GridConfig>,
+    rule_library: Res<RuleLibrary>,
+    topology: Res<Topology>,
+) {
+    let rule_name = rule_library.active_rule().name.clone();
+
+    commands
+        .spawn((
+            MenuRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(12.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Text::new("Conway's Game of Life"),
+                TextFont {
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            root.spawn((
+                Button,
+                Node {
+                    width: Val::Px(220.0),
+                    height: Val::Px(40.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.2, 0.2, 0.25)),
+                GridSizeButton,
+            ))
+            .with_children(|button| {
+                button.spawn((
+                    Text::new(format!("Grid size: {}x{}", grid_config.width, grid_config.height)),
+                    TextFont {
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                    GridSizeLabel,
+                ));
+            });
+
+            root.spawn((
+                Button,
+                Node {
+                    width: Val::Px(220.0),
+                    height: Val::Px(40.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.2, 0.2, 0.25)),
+                RuleButton,
+            ))
+            .with_children(|button| {
+                button.spawn((
+                    Text::new(format!("Rule: {rule_name}")),
+                    TextFont {
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                    RuleLabel,
+                ));
+            });
+
+            root.spawn((
+                Button,
+                Node {
+                    width: Val::Px(220.0),
+                    height: Val::Px(40.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.2, 0.2, 0.25)),
+                TopologyButton,
+            ))
+            .with_children(|button| {
+                button.spawn((
+                    Text::new(format!("Topology: {}", topology.label())),
+                    TextFont {
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                    TopologyLabel,
+                ));
+            });
+
+            crate::ui::spawn_slider_row(root, "Density", MenuDensitySlider, 0.2);
+
+            root.spawn((
+                Button,
+                Node {
+                    width: Val::Px(220.0),
+                    height: Val::Px(48.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.3, 0.55, 0.3)),
+                StartButton,
+            ))
+            .with_children(|button| {
+                button.spawn((
+                    Text::new("Start"),
+                    TextFont {
+                        font_size: 20.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+            });
+        });
+}
+
+#[derive(Component)]
+struct MenuDensitySlider;
+
+fn despawn_menu(mut commands: Commands, query: Query<Entity, With<MenuRoot>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn handle_grid_size_button(
+    interactions: Query<&Interaction, (Changed<Interaction>, With<GridSizeButton>)>,
+    mut labels: Query<&mut Text, With<GridSizeLabel>>,
+    mut grid_config: ResMut<GridConfig>,
+) {
+    for interaction in &interactions {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let current_index = GRID_SIZE_PRESETS
+            .iter()
+            .position(|&(w, h)| w == grid_config.width && h == grid_config.height)
+            .unwrap_or(0);
+        let (width, height) = GRID_SIZE_PRESETS[(current_index + 1) % GRID_SIZE_PRESETS.len()];
+        grid_config.width = width;
+        grid_config.height = height;
+
+        for mut text in &mut labels {
+            **text = format!("Grid size: {width}x{height}");
+        }
+    }
+}
+
+fn handle_rule_button(
+    interactions: Query<&Interaction, (Changed<Interaction>, With<RuleButton>)>,
+    mut labels: Query<&mut Text, With<RuleLabel>>,
+    mut rule_library: ResMut<RuleLibrary>,
+) {
+    for interaction in &interactions {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        rule_library.active = (rule_library.active + 1) % rule_library.rules.len();
+        let name = rule_library.active_rule().name.clone();
+
+        for mut text in &mut labels {
+            **text = format!("Rule: {name}");
+        }
+    }
+}
+
+fn handle_topology_button(
+    interactions: Query<&Interaction, (Changed<Interaction>, With<TopologyButton>)>,
+    mut labels: Query<&mut Text, With<TopologyLabel>>,
+    mut topology: ResMut<Topology>,
+) {
+    for interaction in &interactions {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        *topology = topology.next();
+
+        for mut text in &mut labels {
+            **text = format!("Topology: {}", topology.label());
+        }
+    }
+}
+
+fn handle_start_button(
+    interactions: Query<&Interaction, (Changed<Interaction>, With<StartButton>)>,
+    rule_library: Res<RuleLibrary>,
+    mut active_rule: ResMut<crate::rule::Rule>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for interaction in &interactions {
+        if *interaction == Interaction::Pressed {
+            *active_rule = rule_library.active_rule().clone();
+            next_state.set(AppState::Running);
+        }
+    }
+}
+
+fn spawn_paused_overlay(mut commands: Commands) {
+    commands
+        .spawn((
+            PausedOverlay,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.4)),
+        ))
+        .with_children(|overlay| {
+            overlay.spawn((
+                Text::new("PAUSED"),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn despawn_paused_overlay(mut commands: Commands, query: Query<Entity, With<PausedOverlay>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Mirrors `ui::handle_density_slider`, but for the menu's copy of the
+/// density slider, which drives the same `SeedDensity` resource the game
+/// reads from at `setup_cells`/`reset_grid` time.
+fn handle_menu_density_slider(
+    sliders: Query<
+        (&Interaction, &RelativeCursorPosition, &Children),
+        (With<MenuDensitySlider>, Changed<Interaction>),
+    >,
+    mut fills: Query<&mut Node, With<SliderFill>>,
+    mut seed_density: ResMut<SeedDensity>,
+) {
+    for (interaction, cursor_position, children) in &sliders {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(position) = cursor_position.normalized else {
+            continue;
+        };
+        let fraction = position.x.clamp(0.0, 1.0);
+        seed_density.0 = DENSITY_MIN + fraction * (DENSITY_MAX - DENSITY_MIN);
+
+        for child in children {
+            if let Ok(mut fill_node) = fills.get_mut(*child) {
+                fill_node.width = Val::Percent(fraction * 100.0);
+            }
+        }
+    }
+}
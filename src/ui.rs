@@ -0,0 +1,332 @@
+//! In-game control panel: play/pause, single-step, fast-forward, and
+//! sliders for step speed and seeding density, scrubbable live alongside the
+//! keyboard `toggle_auto_play`/`reset_grid` controls, in the spirit of the
+//! on-screen controls from the asteroids-genetic app. The panel has no
+//! reseed button: reseeding is still the R key (`reset_grid`), which uses
+//! the density slider's current value.
+
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+
+use crate::state::AppState;
+use crate::GameState;
+
+pub const SPEED_MIN: f32 = 0.05;
+pub const SPEED_MAX: f32 = 1.0;
+pub const DENSITY_MIN: f32 = 0.0;
+pub const DENSITY_MAX: f32 = 0.5;
+
+const BUTTON_COLOR: Color = Color::srgb(0.2, 0.2, 0.25);
+const BUTTON_ACTIVE_COLOR: Color = Color::srgb(0.3, 0.55, 0.3);
+const SLIDER_TRACK_COLOR: Color = Color::srgb(0.15, 0.15, 0.18);
+const SLIDER_FILL_COLOR: Color = Color::srgb(0.4, 0.6, 0.8);
+
+/// The probability a cell is seeded alive, read by `setup_cells` and
+/// `reset_grid` instead of a hardcoded constant.
+#[derive(Resource)]
+pub struct SeedDensity(pub f32);
+
+impl Default for SeedDensity {
+    fn default() -> Self {
+        Self(0.1)
+    }
+}
+
+#[derive(Component)]
+pub struct PlayPauseButton;
+
+#[derive(Component)]
+pub struct StepButton;
+
+#[derive(Component)]
+pub struct FastForwardButton;
+
+#[derive(Component)]
+struct SpeedSlider;
+
+#[derive(Component)]
+struct DensitySlider;
+
+#[derive(Component)]
+pub(crate) struct SliderFill;
+
+#[derive(Component)]
+struct PlayPauseLabel;
+
+#[derive(Component)]
+struct ControlPanelRoot;
+
+pub struct ControlPanelPlugin;
+
+impl Plugin for ControlPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SeedDensity>()
+            .add_systems(
+                OnEnter(AppState::Running),
+                // `Paused -> Running` re-fires this `OnEnter`, so guard on
+                // the panel not already existing or unpausing would stack a
+                // second one on top of the first every time.
+                spawn_control_panel.run_if(not(any_with_component::<ControlPanelRoot>())),
+            )
+            .add_systems(OnEnter(AppState::Menu), despawn_control_panel)
+            .add_systems(
+                Update,
+                (
+                    handle_play_pause_button,
+                    handle_step_button,
+                    handle_fast_forward_button,
+                    handle_speed_slider,
+                    handle_density_slider,
+                )
+                    .run_if(in_state(AppState::Running).or_else(in_state(AppState::Paused))),
+            );
+    }
+}
+
+fn despawn_control_panel(mut commands: Commands, query: Query<Entity, With<ControlPanelRoot>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn spawn_control_panel(mut commands: Commands, game_state: Res<GameState>) {
+    let speed_fraction =
+        (SPEED_MAX - game_state.step_interval) / (SPEED_MAX - SPEED_MIN);
+
+    commands
+        .spawn((
+            ControlPanelRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(12.0),
+                top: Val::Px(12.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+        ))
+        .with_children(|panel| {
+            panel
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(8.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    spawn_play_pause_button(row);
+                    spawn_button(row, StepButton, "Step");
+                    spawn_button(row, FastForwardButton, "Fast-forward x5");
+                });
+
+            spawn_slider_row(panel, "Speed", SpeedSlider, speed_fraction);
+            spawn_slider_row(panel, "Density", DensitySlider, 0.2);
+        });
+}
+
+pub(crate) fn spawn_button(parent: &mut ChildBuilder, marker: impl Component, label: &str) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(150.0),
+                height: Val::Px(36.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(BUTTON_COLOR),
+            marker,
+        ))
+        .with_children(|button| {
+            button.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn spawn_play_pause_button(parent: &mut ChildBuilder) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(150.0),
+                height: Val::Px(36.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(BUTTON_COLOR),
+            PlayPauseButton,
+        ))
+        .with_children(|button| {
+            button.spawn((
+                Text::new("Play"),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                PlayPauseLabel,
+            ));
+        });
+}
+
+pub(crate) fn spawn_slider_row(
+    parent: &mut ChildBuilder,
+    label: &str,
+    marker: impl Component,
+    fraction: f32,
+) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(8.0),
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            row.spawn((
+                Node {
+                    width: Val::Px(180.0),
+                    height: Val::Px(18.0),
+                    ..default()
+                },
+                BackgroundColor(SLIDER_TRACK_COLOR),
+                RelativeCursorPosition::default(),
+                marker,
+            ))
+            .with_children(|track| {
+                track.spawn((
+                    Node {
+                        width: Val::Percent(fraction * 100.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(SLIDER_FILL_COLOR),
+                    SliderFill,
+                ));
+            });
+        });
+}
+
+fn handle_play_pause_button(
+    interactions: Query<&Interaction, (Changed<Interaction>, With<PlayPauseButton>)>,
+    mut labels: Query<&mut Text, With<PlayPauseLabel>>,
+    mut game_state: ResMut<GameState>,
+) {
+    for interaction in &interactions {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        game_state.auto_play = !game_state.auto_play;
+        game_state.timer.reset();
+
+        for mut text in &mut labels {
+            **text = if game_state.auto_play { "Pause" } else { "Play" }.to_string();
+        }
+    }
+}
+
+fn handle_step_button(
+    interactions: Query<&Interaction, (Changed<Interaction>, With<StepButton>)>,
+    mut game_state: ResMut<GameState>,
+) {
+    for interaction in &interactions {
+        if *interaction == Interaction::Pressed {
+            game_state.step_requested = true;
+        }
+    }
+}
+
+fn handle_fast_forward_button(
+    mut interactions: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<FastForwardButton>),
+    >,
+    mut game_state: ResMut<GameState>,
+) {
+    for (interaction, mut color) in &mut interactions {
+        if *interaction == Interaction::Pressed {
+            game_state.fast_forward = !game_state.fast_forward;
+            *color = BackgroundColor(if game_state.fast_forward {
+                BUTTON_ACTIVE_COLOR
+            } else {
+                BUTTON_COLOR
+            });
+        }
+    }
+}
+
+fn handle_speed_slider(
+    sliders: Query<
+        (&Interaction, &RelativeCursorPosition, &Children),
+        (With<SpeedSlider>, Changed<Interaction>),
+    >,
+    mut fills: Query<&mut Node, With<SliderFill>>,
+    mut game_state: ResMut<GameState>,
+) {
+    for (interaction, cursor_position, children) in &sliders {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(position) = cursor_position.normalized else {
+            continue;
+        };
+        // Higher fraction (dragged further right) means faster, i.e. a
+        // shorter step interval, so the slider reads the same direction as
+        // its "Speed" label.
+        let fraction = position.x.clamp(0.0, 1.0);
+        game_state.step_interval = SPEED_MAX - fraction * (SPEED_MAX - SPEED_MIN);
+        game_state
+            .timer
+            .set_duration(std::time::Duration::from_secs_f32(game_state.step_interval));
+
+        for child in children {
+            if let Ok(mut fill_node) = fills.get_mut(*child) {
+                fill_node.width = Val::Percent(fraction * 100.0);
+            }
+        }
+    }
+}
+
+fn handle_density_slider(
+    sliders: Query<
+        (&Interaction, &RelativeCursorPosition, &Children),
+        (With<DensitySlider>, Changed<Interaction>),
+    >,
+    mut fills: Query<&mut Node, With<SliderFill>>,
+    mut seed_density: ResMut<SeedDensity>,
+) {
+    for (interaction, cursor_position, children) in &sliders {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(position) = cursor_position.normalized else {
+            continue;
+        };
+        let fraction = position.x.clamp(0.0, 1.0);
+        seed_density.0 = DENSITY_MIN + fraction * (DENSITY_MAX - DENSITY_MIN);
+
+        for child in children {
+            if let Ok(mut fill_node) = fills.get_mut(*child) {
+                fill_node.width = Val::Percent(fraction * 100.0);
+            }
+        }
+    }
+}